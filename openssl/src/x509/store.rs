@@ -45,10 +45,35 @@ use std::path::Path;
 use crate::error::ErrorStack;
 use crate::stack::StackRef;
 #[cfg(any(ossl102, libressl261))]
-use crate::x509::verify::X509VerifyFlags;
-use crate::x509::{X509Object, X509};
+use crate::x509::verify::{X509VerifyFlags, X509VerifyParamRef};
+use crate::x509::{X509Crl, X509Object, X509};
 use crate::{cvt, cvt_p};
 
+/// Records a real entry on the OpenSSL error queue and returns it as an
+/// `ErrorStack`, so that a Rust-side conversion failure surfaces as an
+/// inspectable error rather than an empty one (or a panic).
+#[cfg(ossl300)]
+fn invalid_input() -> ErrorStack {
+    unsafe {
+        ffi::ERR_put_error(
+            ffi::ERR_LIB_SYS,
+            0,
+            ffi::ERR_R_PASSED_INVALID_ARGUMENT,
+            concat!(file!(), "\0").as_ptr() as *const libc::c_char,
+            line!() as c_int,
+        );
+    }
+    ErrorStack::get()
+}
+
+/// Converts a filesystem path into a `CString`, surfacing a non-UTF-8 path or
+/// an interior NUL byte as an `ErrorStack` rather than panicking.
+#[cfg(ossl300)]
+fn path_to_cstring(path: &Path) -> Result<CString, ErrorStack> {
+    let s = path.as_os_str().to_str().ok_or_else(invalid_input)?;
+    CString::new(s).map_err(|_| invalid_input())
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct X509Purpose(c_int);
 
@@ -138,6 +163,15 @@ impl X509StoreBuilderRef {
         unsafe { cvt(ffi::X509_STORE_add_cert(self.as_ptr(), cert.as_ptr())).map(|_| ()) }
     }
 
+    /// Adds a certificate revocation list to the certificate store.
+    ///
+    /// This corresponds to [`X509_STORE_add_crl`].
+    ///
+    /// [`X509_STORE_add_crl`]: https://www.openssl.org/docs/man1.1.1/man3/X509_STORE_add_crl.html
+    pub fn add_crl(&mut self, crl: X509Crl) -> Result<(), ErrorStack> {
+        unsafe { cvt(ffi::X509_STORE_add_crl(self.as_ptr(), crl.as_ptr())).map(|_| ()) }
+    }
+
     /// Sets the maximum verification depth, or the maximum number of intermediate CA certificates that can appear in a chain.
     ///
     /// This corresponds to [`X509_STORE_set_depth`].
@@ -182,6 +216,42 @@ impl X509StoreBuilderRef {
         }
     }
 
+    /// Loads trusted certificate(s) into the `X509Store` from a single PEM
+    /// file.
+    ///
+    /// This corresponds to [`X509_STORE_load_file`].
+    ///
+    /// [`X509_STORE_load_file`]: https://www.openssl.org/docs/man3.0/man3/X509_STORE_load_file.html
+    #[cfg(ossl300)]
+    pub fn load_file<P: AsRef<Path>>(&mut self, file: P) -> Result<(), ErrorStack> {
+        let file = path_to_cstring(file.as_ref())?;
+        unsafe { cvt(ffi::X509_STORE_load_file(self.as_ptr(), file.as_ptr())).map(|_| ()) }
+    }
+
+    /// Loads trusted certificate(s) into the `X509Store` from a directory of
+    /// hashed certificates.
+    ///
+    /// This corresponds to [`X509_STORE_load_path`].
+    ///
+    /// [`X509_STORE_load_path`]: https://www.openssl.org/docs/man3.0/man3/X509_STORE_load_path.html
+    #[cfg(ossl300)]
+    pub fn load_path<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), ErrorStack> {
+        let dir = path_to_cstring(dir.as_ref())?;
+        unsafe { cvt(ffi::X509_STORE_load_path(self.as_ptr(), dir.as_ptr())).map(|_| ()) }
+    }
+
+    /// Loads trusted certificate(s) into the `X509Store` from an `OSSL_STORE`
+    /// source addressed by a URI.
+    ///
+    /// This corresponds to [`X509_STORE_load_store`].
+    ///
+    /// [`X509_STORE_load_store`]: https://www.openssl.org/docs/man3.0/man3/X509_STORE_load_store.html
+    #[cfg(ossl300)]
+    pub fn load_store(&mut self, uri: &str) -> Result<(), ErrorStack> {
+        let uri = CString::new(uri).map_err(|_| invalid_input())?;
+        unsafe { cvt(ffi::X509_STORE_load_store(self.as_ptr(), uri.as_ptr())).map(|_| ()) }
+    }
+
     /// Load certificates from their default locations.
     ///
     /// These locations are read from the `SSL_CERT_FILE` and `SSL_CERT_DIR`
@@ -213,6 +283,51 @@ impl X509StoreBuilderRef {
     pub fn set_flags(&mut self, flags: X509VerifyFlags) -> Result<(), ErrorStack> {
         unsafe { cvt(ffi::X509_STORE_set_flags(self.as_ptr(), flags.bits())).map(|_| ()) }
     }
+
+    /// Returns the maximum verification depth configured on the store.
+    // `X509_VERIFY_PARAM_get_depth` is not reliably exported by LibreSSL, so
+    // this getter is OpenSSL-only even though `set_depth` is available more
+    // broadly.
+    #[cfg(ossl102)]
+    pub fn depth(&self) -> i32 {
+        unsafe { ffi::X509_VERIFY_PARAM_get_depth(self.verify_param().as_ptr()) }
+    }
+
+    /// Returns the certificate chain validation related flags configured on
+    /// the store.
+    // `X509_VERIFY_PARAM_get_flags` is not reliably exported by LibreSSL, so
+    // this getter is OpenSSL-only even though `set_flags` is available more
+    // broadly.
+    #[cfg(ossl102)]
+    pub fn flags(&self) -> X509VerifyFlags {
+        unsafe {
+            X509VerifyFlags::from_bits_truncate(ffi::X509_VERIFY_PARAM_get_flags(
+                self.verify_param().as_ptr(),
+            ))
+        }
+    }
+
+    /// Returns a reference to the `X509VerifyParam` holding the store's
+    /// verification parameters.
+    ///
+    /// This corresponds to [`X509_STORE_get0_param`].
+    ///
+    /// [`X509_STORE_get0_param`]: https://www.openssl.org/docs/man1.1.1/man3/X509_STORE_get0_param.html
+    #[cfg(any(ossl102, libressl261))]
+    pub fn verify_param(&self) -> &X509VerifyParamRef {
+        unsafe { X509VerifyParamRef::from_ptr(ffi::X509_STORE_get0_param(self.as_ptr())) }
+    }
+
+    /// Returns a mutable reference to the `X509VerifyParam` holding the
+    /// store's verification parameters.
+    ///
+    /// This corresponds to [`X509_STORE_get0_param`].
+    ///
+    /// [`X509_STORE_get0_param`]: https://www.openssl.org/docs/man1.1.1/man3/X509_STORE_get0_param.html
+    #[cfg(any(ossl102, libressl261))]
+    pub fn verify_param_mut(&mut self) -> &mut X509VerifyParamRef {
+        unsafe { X509VerifyParamRef::from_ptr_mut(ffi::X509_STORE_get0_param(self.as_ptr())) }
+    }
 }
 
 generic_foreign_type_and_impl_send_sync! {
@@ -268,6 +383,117 @@ impl X509LookupRef<HashDir> {
     }
 }
 
+/// Marker type corresponding to the [`X509_LOOKUP_store`] lookup method.
+///
+/// [`X509_LOOKUP_store`]: https://www.openssl.org/docs/man3.0/man3/X509_LOOKUP_store.html
+#[cfg(ossl300)]
+pub struct Store;
+
+#[cfg(ossl300)]
+impl X509Lookup<Store> {
+    /// Lookup method that loads certificates and CRLs on demand from an
+    /// `OSSL_STORE`-backed source addressed by a URI, caching them in memory
+    /// once they are loaded.
+    ///
+    /// This corresponds to [`X509_LOOKUP_store`].
+    ///
+    /// [`X509_LOOKUP_store`]: https://www.openssl.org/docs/man3.0/man3/X509_LOOKUP_store.html
+    pub fn store() -> &'static X509LookupMethodRef<Store> {
+        unsafe { X509LookupMethodRef::from_ptr(ffi::X509_LOOKUP_store()) }
+    }
+}
+
+#[cfg(ossl300)]
+impl X509LookupRef<Store> {
+    /// Adds a URI from which certificates and CRLs will be loaded on-demand.
+    /// Must be used with `X509Lookup::store`.
+    ///
+    /// This corresponds to [`X509_LOOKUP_add_store`].
+    ///
+    /// [`X509_LOOKUP_add_store`]: https://www.openssl.org/docs/man3.0/man3/X509_LOOKUP_add_store.html
+    pub fn add_store(&mut self, uri: &str) -> Result<(), ErrorStack> {
+        let uri = CString::new(uri).unwrap();
+        unsafe { cvt(ffi::X509_LOOKUP_add_store(self.as_ptr(), uri.as_ptr())).map(|_| ()) }
+    }
+}
+
+/// Marker type corresponding to the [`X509_LOOKUP_file`] lookup method.
+///
+/// [`X509_LOOKUP_file`]: https://www.openssl.org/docs/man1.1.0/crypto/X509_LOOKUP_file.html
+pub struct File;
+
+impl X509Lookup<File> {
+    /// Lookup method that loads all the certificates or CRLs present in a
+    /// single concatenated PEM file (or the single certificate in a DER file)
+    /// into memory at the time the file is added.
+    ///
+    /// This corresponds to [`X509_LOOKUP_file`].
+    ///
+    /// [`X509_LOOKUP_file`]: https://www.openssl.org/docs/man1.1.0/crypto/X509_LOOKUP_file.html
+    pub fn file() -> &'static X509LookupMethodRef<File> {
+        unsafe { X509LookupMethodRef::from_ptr(ffi::X509_LOOKUP_file()) }
+    }
+}
+
+impl X509LookupRef<File> {
+    /// Specifies a file from which certificates will be loaded. Must be used
+    /// with `X509Lookup::file`.
+    ///
+    /// Note that with `SslFiletype::PEM` the `file` lookup loads every
+    /// certificate *and* CRL present in the bundle with a single
+    /// `X509_LOOKUP_load_file` call; with `SslFiletype::ASN1` it reads a single
+    /// DER-encoded certificate. This method and
+    /// [`load_crl_file`](Self::load_crl_file) differ only in intent, not in
+    /// what they actually load.
+    ///
+    /// This corresponds to [`X509_LOOKUP_load_file`].
+    ///
+    /// [`X509_LOOKUP_load_file`]: https://www.openssl.org/docs/man1.1.1/man3/X509_LOOKUP_load_file.html
+    pub fn load_cert_file(
+        &mut self,
+        name: &str,
+        file_type: crate::ssl::SslFiletype,
+    ) -> Result<(), ErrorStack> {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            cvt(ffi::X509_LOOKUP_load_file(
+                self.as_ptr(),
+                name.as_ptr(),
+                file_type.as_raw(),
+            ))
+            .map(|_| ())
+        }
+    }
+
+    /// Specifies a PEM file from which CRLs will be loaded. Must be used with
+    /// `X509Lookup::file`.
+    ///
+    /// As with [`load_cert_file`](Self::load_cert_file), the underlying
+    /// `X509_LOOKUP_load_file` call loads both certificates and CRLs from a
+    /// PEM bundle; this name documents the caller's intent rather than
+    /// restricting the load to CRLs only. With `SslFiletype::ASN1` the file is
+    /// read as a single DER-encoded certificate, so a DER CRL is not loaded.
+    ///
+    /// This corresponds to [`X509_LOOKUP_load_file`].
+    ///
+    /// [`X509_LOOKUP_load_file`]: https://www.openssl.org/docs/man1.1.1/man3/X509_LOOKUP_load_file.html
+    pub fn load_crl_file(
+        &mut self,
+        name: &str,
+        file_type: crate::ssl::SslFiletype,
+    ) -> Result<(), ErrorStack> {
+        let name = CString::new(name).unwrap();
+        unsafe {
+            cvt(ffi::X509_LOOKUP_load_file(
+                self.as_ptr(),
+                name.as_ptr(),
+                file_type.as_raw(),
+            ))
+            .map(|_| ())
+        }
+    }
+}
+
 generic_foreign_type_and_impl_send_sync! {
     type CType = ffi::X509_LOOKUP_METHOD;
     fn drop = X509_LOOKUP_meth_free;
@@ -293,6 +519,51 @@ impl X509StoreRef {
     pub fn objects(&self) -> &StackRef<X509Object> {
         unsafe { StackRef::from_ptr(X509_STORE_get0_objects(self.as_ptr())) }
     }
+
+    /// Returns the maximum verification depth configured on the store.
+    // `X509_VERIFY_PARAM_get_depth` is not reliably exported by LibreSSL, so
+    // this getter is OpenSSL-only even though `set_depth` is available more
+    // broadly.
+    #[cfg(ossl102)]
+    pub fn depth(&self) -> i32 {
+        unsafe { ffi::X509_VERIFY_PARAM_get_depth(self.verify_param().as_ptr()) }
+    }
+
+    /// Returns the certificate chain validation related flags configured on
+    /// the store.
+    // `X509_VERIFY_PARAM_get_flags` is not reliably exported by LibreSSL, so
+    // this getter is OpenSSL-only even though `set_flags` is available more
+    // broadly.
+    #[cfg(ossl102)]
+    pub fn flags(&self) -> X509VerifyFlags {
+        unsafe {
+            X509VerifyFlags::from_bits_truncate(ffi::X509_VERIFY_PARAM_get_flags(
+                self.verify_param().as_ptr(),
+            ))
+        }
+    }
+
+    /// Returns a reference to the `X509VerifyParam` holding the store's
+    /// verification parameters.
+    ///
+    /// This corresponds to [`X509_STORE_get0_param`].
+    ///
+    /// [`X509_STORE_get0_param`]: https://www.openssl.org/docs/man1.1.1/man3/X509_STORE_get0_param.html
+    #[cfg(any(ossl102, libressl261))]
+    pub fn verify_param(&self) -> &X509VerifyParamRef {
+        unsafe { X509VerifyParamRef::from_ptr(ffi::X509_STORE_get0_param(self.as_ptr())) }
+    }
+
+    /// Returns a mutable reference to the `X509VerifyParam` holding the
+    /// store's verification parameters.
+    ///
+    /// This corresponds to [`X509_STORE_get0_param`].
+    ///
+    /// [`X509_STORE_get0_param`]: https://www.openssl.org/docs/man1.1.1/man3/X509_STORE_get0_param.html
+    #[cfg(any(ossl102, libressl261))]
+    pub fn verify_param_mut(&mut self) -> &mut X509VerifyParamRef {
+        unsafe { X509VerifyParamRef::from_ptr_mut(ffi::X509_STORE_get0_param(self.as_ptr())) }
+    }
 }
 
 cfg_if! {
@@ -314,3 +585,27 @@ cfg_if! {
         unsafe fn X509_LOOKUP_meth_free(_x: *mut ffi::X509_LOOKUP_METHOD) {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(ossl102)]
+    fn verify_param_round_trips_setters() {
+        let mut builder = X509StoreBuilder::new().unwrap();
+
+        builder.set_depth(7).unwrap();
+        builder.set_flags(X509VerifyFlags::CRL_CHECK).unwrap();
+
+        assert_eq!(builder.depth(), 7);
+        assert!(builder.flags().contains(X509VerifyFlags::CRL_CHECK));
+
+        // The param is reachable for further inspection/customization.
+        let _ = builder.verify_param();
+
+        let store = builder.build();
+        assert_eq!(store.depth(), 7);
+        assert!(store.flags().contains(X509VerifyFlags::CRL_CHECK));
+    }
+}